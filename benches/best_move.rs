@@ -0,0 +1,36 @@
+use std::hint::black_box;
+use std::time::Instant;
+use tic_tac_toe::{Board, Cell};
+
+fn bench(name: &str, iterations: u32, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let per_iter = start.elapsed() / iterations;
+    println!("{:<32} {:>12?} / iter", name, per_iter);
+}
+
+fn main() {
+    let empty = Board::new();
+    bench("best_move empty (fast path)", 1_000_000, || {
+        black_box(black_box(&empty).best_move(Cell::X));
+    });
+    bench("scored_moves empty (full search)", 5, || {
+        black_box(black_box(&empty).scored_moves(Cell::X));
+    });
+
+    let mut one = Board::new();
+    one.cells[0] = Cell::X;
+    bench("best_move after one move", 10, || {
+        black_box(black_box(&one).best_move(Cell::O));
+    });
+
+    let mut mid = Board::new();
+    mid.cells[0] = Cell::X;
+    mid.cells[4] = Cell::O;
+    mid.cells[8] = Cell::X;
+    bench("best_move mid-game", 1_000, || {
+        black_box(black_box(&mid).best_move(Cell::O));
+    });
+}
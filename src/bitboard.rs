@@ -0,0 +1,96 @@
+use crate::{Board, Cell, WIN_LINES};
+
+const fn line_masks() -> [u16; 8] {
+    let mut masks = [0u16; 8];
+    let mut i = 0;
+    while i < 8 {
+        let [a, b, c] = WIN_LINES[i];
+        masks[i] = (1 << a) | (1 << b) | (1 << c);
+        i += 1;
+    }
+    masks
+}
+
+/// `WIN_LINES` as bitmasks, in the same order.
+const LINE_MASKS: [u16; 8] = line_masks();
+
+/// The board as one bitmask per player, bit `i` standing for cell `i`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) struct BitBoard {
+    pub x: u16,
+    pub o: u16,
+}
+
+impl BitBoard {
+    pub fn check_winner(&self) -> Option<Cell> {
+        for mask in LINE_MASKS {
+            if self.x & mask == mask {
+                return Some(Cell::X);
+            }
+            if self.o & mask == mask {
+                return Some(Cell::O);
+            }
+        }
+        None
+    }
+}
+
+impl From<&Board> for BitBoard {
+    fn from(board: &Board) -> BitBoard {
+        let mut bits = BitBoard::default();
+        for (i, cell) in board.cells.iter().enumerate() {
+            match cell {
+                Cell::X => bits.x |= 1 << i,
+                Cell::O => bits.o |= 1 << i,
+                Cell::Empty => {}
+            }
+        }
+        bits
+    }
+}
+
+impl From<BitBoard> for Board {
+    fn from(bits: BitBoard) -> Board {
+        let mut board = Board::new();
+        for i in 0..9 {
+            if bits.x & (1 << i) != 0 {
+                board.cells[i] = Cell::X;
+            } else if bits.o & (1 << i) != 0 {
+                board.cells[i] = Cell::O;
+            }
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::{gen_index, XorShiftRng};
+
+    #[test]
+    fn test_round_trip() {
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::O;
+        board.cells[8] = Cell::X;
+
+        let bits = BitBoard::from(&board);
+        assert_eq!(bits, BitBoard { x: 0b1_0000_0001, o: 0b1_0000 });
+        assert!(Board::from(bits) == board);
+    }
+
+    #[test]
+    fn test_matches_array_winner() {
+        let mut rng = XorShiftRng::new(2024);
+        let cells = [Cell::Empty, Cell::X, Cell::O];
+        for _ in 0..5000 {
+            let mut board = Board::new();
+            for i in 0..9 {
+                board.cells[i] = cells[gen_index(&mut rng, 3)];
+            }
+            let expected = board.winning_line().map(|line| board.cells[line[0]]);
+            assert_eq!(BitBoard::from(&board).check_winner(), expected);
+        }
+    }
+}
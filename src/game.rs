@@ -0,0 +1,364 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Board, Cell, GameStatus, MoveError};
+
+/// Why `replay` stopped. `position` is the 0-based index into the move list.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ReplayError {
+    /// The token is not a cell index.
+    Parse { position: usize, token: String },
+    /// The move is out of range, on a taken cell or after the game ended.
+    Move { position: usize, error: MoveError },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Parse { position, token } => {
+                write!(f, "move {}: {:?} is not a cell index", position, token)
+            }
+            ReplayError::Move { position, error } => write!(f, "move {}: {}", position, error),
+        }
+    }
+}
+
+impl core::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ReplayError::Move { error, .. } => Some(error),
+            ReplayError::Parse { .. } => None,
+        }
+    }
+}
+
+/// A board plus the turn order, so the side to move can be worked out from
+/// the marks already placed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Game {
+    pub board: Board,
+    first: Cell,
+    human: Cell,
+    x_label: Option<String>,
+    o_label: Option<String>,
+}
+
+impl Game {
+    /// Starts an empty game where `first` moves first. `Cell::Empty` is
+    /// treated as X.
+    pub fn new(first: Cell) -> Game {
+        let first = if first == Cell::O { Cell::O } else { Cell::X };
+        Game { board: Board::new(), first, human: first, x_label: None, o_label: None }
+    }
+
+    pub fn first_player(&self) -> Cell {
+        self.first
+    }
+
+    /// The side `step` asks for moves. Defaults to the first player.
+    pub fn human(&self) -> Cell {
+        self.human
+    }
+
+    /// Sets which side is human. `Cell::Empty` lets the computer play both.
+    pub fn set_human(&mut self, human: Cell) {
+        self.human = human;
+    }
+
+    /// Names `player` in messages instead of its symbol. Ignored for
+    /// `Cell::Empty`.
+    pub fn set_label(&mut self, player: Cell, label: &str) {
+        match player {
+            Cell::X => self.x_label = Some(label.to_string()),
+            Cell::O => self.o_label = Some(label.to_string()),
+            Cell::Empty => {}
+        }
+    }
+
+    /// The label set for `player`, or its symbol.
+    pub fn player_name(&self, player: Cell) -> String {
+        let label = match player {
+            Cell::X => &self.x_label,
+            Cell::O => &self.o_label,
+            Cell::Empty => &None,
+        };
+        label.clone().unwrap_or_else(|| player.to_string())
+    }
+
+    pub fn winner_name(&self) -> Option<String> {
+        match self.status() {
+            GameStatus::Won(winner) => Some(self.player_name(winner)),
+            _ => None,
+        }
+    }
+
+    /// The end-of-game line for the CLI, e.g. "X (you) wins!" or "Draw.",
+    /// from the point of view of `human`.
+    pub fn result_message(&self, human: Cell) -> String {
+        match self.status() {
+            GameStatus::Won(winner) if winner == human => {
+                format!("{} (you) wins!", self.player_name(winner))
+            }
+            GameStatus::Won(winner) => format!("{} wins!", self.player_name(winner)),
+            GameStatus::Draw => String::from("Draw."),
+            GameStatus::InProgress => String::from("Game in progress."),
+        }
+    }
+
+    fn second_player(&self) -> Cell {
+        if self.first == Cell::X { Cell::O } else { Cell::X }
+    }
+
+    fn counts(&self) -> (usize, usize) {
+        let count = |p| self.board.cells.iter().filter(|&&c| c == p).count();
+        (count(self.first), count(self.second_player()))
+    }
+
+    pub fn current_player(&self) -> Cell {
+        let (first, second) = self.counts();
+        if first > second { self.second_player() } else { self.first }
+    }
+
+    /// Whether the board could have been reached by alternating moves
+    /// starting with the first player, stopping once someone won.
+    pub fn is_valid(&self) -> bool {
+        let (first, second) = self.counts();
+        if first != second && first != second + 1 {
+            return false;
+        }
+        match self.board.check_winner_strict() {
+            Err(_) => false,
+            Ok(Some(winner)) if winner == self.first => first == second + 1,
+            Ok(Some(_)) => first == second,
+            Ok(None) => true,
+        }
+    }
+
+    pub fn status(&self) -> GameStatus {
+        self.board.status()
+    }
+
+    /// A short report of the position: the status, the side to move and the
+    /// minimax score of each legal move, e.g.
+    ///
+    /// ```text
+    /// Status: in progress
+    /// To move: O
+    /// Scores: 1=-1 2=0 3=-1
+    /// ```
+    pub fn analysis(&self) -> String {
+        let status = match self.status() {
+            GameStatus::InProgress => String::from("in progress"),
+            GameStatus::Won(winner) => format!("{} wins", winner),
+            GameStatus::Draw => String::from("draw"),
+        };
+        if self.status() != GameStatus::InProgress {
+            return format!("Status: {}\n", status);
+        }
+
+        let player = self.current_player();
+        let scores: Vec<String> = self
+            .board
+            .scored_moves(player)
+            .iter()
+            .map(|(i, score)| format!("{}={}", i, score))
+            .collect();
+        format!("Status: {}\nTo move: {}\nScores: {}\n", status, player, scores.join(" "))
+    }
+
+    /// Plays `index` for the side to move.
+    pub fn play(&mut self, index: usize) -> Result<(), MoveError> {
+        let player = self.current_player();
+        self.board.apply_move(index, player)
+    }
+}
+
+/// Plays whitespace-separated cell indices such as `"4 0 8 2 6"` in turn,
+/// starting with `first`, and returns the final board and status.
+pub fn replay(moves: &str, first: Cell) -> Result<(Board, GameStatus), ReplayError> {
+    let mut game = Game::new(first);
+    for (position, token) in moves.split_whitespace().enumerate() {
+        let index = token.parse::<usize>().map_err(|_| ReplayError::Parse {
+            position,
+            token: token.to_string(),
+        })?;
+        game.play(index)
+            .map_err(|error| ReplayError::Move { position, error })?;
+    }
+    let status = game.status();
+    Ok((game.board, status))
+}
+
+/// Plays one turn: asks `get_human_move` when it is the human's turn, calling
+/// it again until it returns a legal move, otherwise plays `best_move`.
+/// Does nothing once the game is over.
+pub fn step<F>(game: &mut Game, mut get_human_move: F) -> GameStatus
+where
+    F: FnMut(&Board) -> usize,
+{
+    if game.status() != GameStatus::InProgress {
+        return game.status();
+    }
+    if game.current_player() == game.human {
+        while game.play(get_human_move(&game.board)).is_err() {}
+    } else if let Some(idx) = game.board.best_move(game.current_player()) {
+        game.play(idx).expect("best_move returned an illegal move");
+    }
+    game.status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_o_first_alternation() {
+        let mut game = Game::new(Cell::O);
+        assert_eq!(game.first_player(), Cell::O);
+        assert_eq!(game.current_player(), Cell::O);
+
+        game.play(4).unwrap();
+        assert_eq!(game.board.cells[4], Cell::O);
+        assert_eq!(game.current_player(), Cell::X);
+
+        game.play(0).unwrap();
+        assert_eq!(game.board.cells[0], Cell::X);
+        assert_eq!(game.current_player(), Cell::O);
+        assert!(game.is_valid());
+    }
+
+    #[test]
+    fn test_replay() {
+        let (board, status) = replay("4 0 8 2 1 7 6 3 5", Cell::X).unwrap();
+        assert_eq!(status, GameStatus::Draw);
+        assert_eq!(board.cells[4], Cell::X);
+        assert_eq!(board.cells[0], Cell::O);
+
+        let (board, status) = replay("0 4", Cell::O).unwrap();
+        assert_eq!(status, GameStatus::InProgress);
+        assert_eq!(board.cells[0], Cell::O);
+        assert_eq!(board.cells[4], Cell::X);
+
+        assert_eq!(
+            replay("4 0 4", Cell::X).unwrap_err(),
+            ReplayError::Move { position: 2, error: MoveError::Occupied(4) }
+        );
+        assert_eq!(
+            replay("0 3 1 4 2 5", Cell::X).unwrap_err(),
+            ReplayError::Move { position: 5, error: MoveError::GameOver }
+        );
+        assert_eq!(
+            replay("4 x", Cell::X).unwrap_err(),
+            ReplayError::Parse { position: 1, token: "x".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_step_scripted_game() {
+        for human in [Cell::X, Cell::O] {
+            let mut game = Game::new(Cell::X);
+            game.set_human(human);
+
+            // Every other answer is out of range, so step has to ask again.
+            let mut asked = 0;
+            let mut status = GameStatus::InProgress;
+            while status == GameStatus::InProgress {
+                status = step(&mut game, |board| {
+                    asked += 1;
+                    if asked % 2 == 1 {
+                        return 9;
+                    }
+                    (0..9).find(|&i| board.cells[i] == Cell::Empty).unwrap()
+                });
+            }
+            assert!(asked > 0);
+            assert_ne!(status, GameStatus::Won(human));
+            assert_eq!(step(&mut game, |_| unreachable!()), status);
+        }
+
+        let mut game = Game::new(Cell::X);
+        game.set_human(Cell::Empty);
+        while step(&mut game, |_| unreachable!()) == GameStatus::InProgress {}
+        assert_eq!(game.status(), GameStatus::Draw);
+    }
+
+    #[test]
+    fn test_analysis() {
+        let mut game = Game::new(Cell::X);
+        for idx in [0, 4, 1, 2, 6, 3, 5] {
+            game.play(idx).unwrap();
+        }
+        assert_eq!(
+            game.analysis(),
+            "Status: in progress\nTo move: O\nScores: 7=0 8=0\n"
+        );
+
+        game.play(8).unwrap();
+        game.play(7).unwrap();
+        assert_eq!(game.analysis(), "Status: draw\n");
+    }
+
+    #[test]
+    fn test_replay_error_display() {
+        let err = replay("4 0 4", Cell::X).unwrap_err();
+        assert_eq!(err.to_string(), "move 2: cell 4 is already occupied");
+        assert!(core::error::Error::source(&err).is_some());
+
+        let err = replay("4 b2", Cell::X).unwrap_err();
+        assert_eq!(err.to_string(), "move 1: \"b2\" is not a cell index");
+    }
+
+    #[test]
+    fn test_result_message() {
+        let mut game = Game::new(Cell::X);
+        for idx in [0, 3, 1, 4, 2] {
+            game.play(idx).unwrap();
+        }
+        assert_eq!(game.result_message(Cell::X), "X (you) wins!");
+        assert_eq!(game.result_message(Cell::O), "X wins!");
+        assert_eq!(game.winner_name().as_deref(), Some("X"));
+
+        let mut game = Game::new(Cell::X);
+        game.set_label(Cell::O, "Computer");
+        for idx in [0, 3, 1, 4, 8, 5] {
+            game.play(idx).unwrap();
+        }
+        assert_eq!(game.result_message(Cell::X), "Computer wins!");
+        game.set_label(Cell::O, "Ada");
+        assert_eq!(game.result_message(Cell::O), "Ada (you) wins!");
+        assert_eq!(game.winner_name().as_deref(), Some("Ada"));
+
+        let (board, _) = replay("4 0 8 2 1 7 6 3 5", Cell::X).unwrap();
+        let mut game = Game::new(Cell::X);
+        game.board = board;
+        assert_eq!(game.result_message(Cell::X), "Draw.");
+        assert_eq!(game.result_message(Cell::O), "Draw.");
+        assert_eq!(game.winner_name(), None);
+    }
+
+    #[test]
+    fn test_is_valid_respects_first_player() {
+        let mut game = Game::new(Cell::O);
+        game.board.cells[0] = Cell::X;
+        assert!(!game.is_valid());
+
+        game.board.cells[4] = Cell::O;
+        assert!(game.is_valid());
+
+        // O moved first, so an X win must leave the counts level.
+        let mut game = Game::new(Cell::O);
+        for i in [0, 1, 2] {
+            game.board.cells[i] = Cell::X;
+        }
+        for i in [3, 4] {
+            game.board.cells[i] = Cell::O;
+        }
+        assert!(!game.is_valid());
+        game.board.cells[8] = Cell::O;
+        assert!(game.is_valid());
+
+        assert_eq!(Game::new(Cell::Empty).first_player(), Cell::X);
+    }
+}
@@ -1,16 +1,53 @@
-use std::fmt;
-#[cfg(not(target_arch = "wasm32"))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
 use std::io;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
 use std::io::Write;
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+mod bitboard;
+mod game;
+mod rng;
+mod tree;
+
+pub use game::{replay, step, Game, ReplayError};
+pub use rng::{RngCore, XorShiftRng};
+pub use tree::MoveTree;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
 pub enum Cell {
+    #[default]
     Empty,
     X,
     O,
 }
 
+impl Cell {
+    /// Decodes the compact form used by the WASM bindings: 0 empty, 1 X, 2 O.
+    pub fn from_u8(v: u8) -> Option<Cell> {
+        match v {
+            0 => Some(Cell::Empty),
+            1 => Some(Cell::X),
+            2 => Some(Cell::O),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Cell::Empty => 0,
+            Cell::X => 1,
+            Cell::O => 2,
+        }
+    }
+}
+
 impl fmt::Display for Cell {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -21,7 +58,130 @@ impl fmt::Display for Cell {
     }
 }
 
-const WIN_CONDITIONS: [[usize; 3]; 8] = [
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameStatus {
+    InProgress,
+    Won(Cell),
+    Draw,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveError {
+    OutOfRange(usize),
+    Occupied(usize),
+    /// `Cell::Empty` was given where a player was expected.
+    InvalidPlayer,
+    /// The game has already been won or drawn.
+    GameOver,
+}
+
+/// A board that could not arise from legal play.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoardError {
+    /// Both X and O have a completed line.
+    BothPlayersWon,
+}
+
+/// Why a byte slice could not be read as a `Board`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseBoardError {
+    /// The slice did not hold exactly nine cells.
+    WrongLength(usize),
+    /// A value other than 0, 1 or 2 was found at `index`.
+    InvalidValue { index: usize, value: u8 },
+}
+
+/// Why typed input could not be read as a move by `parse_move`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseMoveError {
+    /// The input was not a non-negative whole number.
+    NotANumber,
+    /// The number is not a cell index, including numbers too large for a
+    /// `usize`.
+    OutOfRange,
+    Occupied(usize),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::OutOfRange(i) => write!(f, "cell {} is out of range (expected 0-8)", i),
+            MoveError::Occupied(i) => write!(f, "cell {} is already occupied", i),
+            MoveError::InvalidPlayer => write!(f, "expected X or O, got an empty cell"),
+            MoveError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl core::error::Error for MoveError {}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::BothPlayersWon => write!(f, "both X and O have a completed line"),
+        }
+    }
+}
+
+impl core::error::Error for BoardError {}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseBoardError::WrongLength(n) => write!(f, "expected 9 cells, got {}", n),
+            ParseBoardError::InvalidValue { index, value } => write!(
+                f,
+                "invalid value {} at cell {} (expected 0, 1 or 2)",
+                value, index
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseBoardError {}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMoveError::NotANumber => write!(f, "expected a cell number from 0 to 8"),
+            ParseMoveError::OutOfRange => write!(f, "cell number is out of range (expected 0-8)"),
+            ParseMoveError::Occupied(i) => write!(f, "cell {} is already occupied", i),
+        }
+    }
+}
+
+impl core::error::Error for ParseMoveError {}
+
+/// Why `hint` recommends a move, strongest first.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum HintReason {
+    /// Completes a line.
+    Win,
+    /// Stops the opponent completing a line next turn.
+    Block,
+    /// Creates two threats at once.
+    Fork,
+    /// Defuses a fork the opponent could otherwise create.
+    BlockFork,
+    /// No tactics apply; the center is the best square.
+    Center,
+    Corner,
+    Edge,
+}
+
+/// How hard the computer tries when picking a move.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Difficulty {
+    /// Any legal move, chosen at random.
+    Easy,
+    /// Takes an immediate win or blocks an immediate loss, otherwise random.
+    Medium,
+    /// Perfect play via `best_move`.
+    Hard,
+}
+
+/// Every row, column and diagonal that wins the game, by cell index.
+pub const WIN_LINES: [[usize; 3]; 8] = [
     [0, 1, 2],
     [3, 4, 5],
     [6, 7, 8],
@@ -32,20 +192,64 @@ const WIN_CONDITIONS: [[usize; 3]; 8] = [
     [2, 4, 6],
 ];
 
+/// How `to_emoji` draws an X.
+pub const EMOJI_X: &str = "❌";
+/// How `to_emoji` draws an O.
+pub const EMOJI_O: &str = "⭕";
+/// How `to_emoji` draws an empty cell.
+pub const EMOJI_EMPTY: &str = "⬜";
+
+/// The middle cell, on four winning lines.
+pub const CENTER: usize = 4;
+/// The corner cells, each on three winning lines.
+pub const CORNERS: [usize; 4] = [0, 2, 6, 8];
+/// The edge cells between the corners, each on two winning lines.
+pub const EDGES: [usize; 4] = [1, 3, 5, 7];
+
+/// The entries of `WIN_LINES` that pass through `index`.
+pub fn lines_through(index: usize) -> impl Iterator<Item = [usize; 3]> {
+    WIN_LINES.into_iter().filter(move |line| line.contains(&index))
+}
+
 /// The board layout is:
 ///   [0][1][2]
 ///   [3][4][5]
 ///   [6][7][8]
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Board {
     pub cells: [Cell; 9],
 }
 
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
+}
+
+/// Reads nine cells in the `Cell::from_u8` encoding, as produced by the
+/// WASM `get_cells`.
+impl TryFrom<&[u8]> for Board {
+    type Error = ParseBoardError;
+
+    fn try_from(bytes: &[u8]) -> Result<Board, ParseBoardError> {
+        if bytes.len() != 9 {
+            return Err(ParseBoardError::WrongLength(bytes.len()));
+        }
+        let mut board = Board::new();
+        for (index, &value) in bytes.iter().enumerate() {
+            board.cells[index] =
+                Cell::from_u8(value).ok_or(ParseBoardError::InvalidValue { index, value })?;
+        }
+        Ok(board)
+    }
+}
+
 impl Board {
     pub fn new() -> Board {
         Board { cells: [Cell::Empty; 9] }
     }
 
+    #[cfg(feature = "std")]
     pub fn print(&self) {
         for row in 0..3 {
             for col in 0..3 {
@@ -56,8 +260,255 @@ impl Board {
         }
     }
 
+    pub fn status(&self) -> GameStatus {
+        if let Some(winner) = self.check_winner() {
+            GameStatus::Won(winner)
+        } else if self.is_full() {
+            GameStatus::Draw
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    /// Places `player` at `index`. Fails without touching the board if the
+    /// move is illegal or the game is already over.
+    pub fn apply_move(&mut self, index: usize, player: Cell) -> Result<(), MoveError> {
+        if player == Cell::Empty {
+            return Err(MoveError::InvalidPlayer);
+        }
+        if index >= 9 {
+            return Err(MoveError::OutOfRange(index));
+        }
+        if self.status() != GameStatus::InProgress {
+            return Err(MoveError::GameOver);
+        }
+        if self.cells[index] != Cell::Empty {
+            return Err(MoveError::Occupied(index));
+        }
+        self.cells[index] = player;
+        Ok(())
+    }
+
+    /// Applies each move in order. On the first illegal one the board is
+    /// restored to how it was before the call, and the error is returned with
+    /// the failing move's position in `moves`.
+    pub fn apply_moves(&mut self, moves: &[(usize, Cell)]) -> Result<(), (usize, MoveError)> {
+        let before = self.clone();
+        for (position, &(index, player)) in moves.iter().enumerate() {
+            if let Err(e) = self.apply_move(index, player) {
+                *self = before;
+                return Err((position, e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Empties `index` and returns what was there. Meant for position
+    /// editors; no game rules are checked.
+    pub fn clear_cell(&mut self, index: usize) -> Result<Cell, MoveError> {
+        if index >= 9 {
+            return Err(MoveError::OutOfRange(index));
+        }
+        Ok(core::mem::take(&mut self.cells[index]))
+    }
+
+    /// Overwrites `index` with any `Cell`, including `Empty`, ignoring game
+    /// rules.
+    pub fn set_cell(&mut self, index: usize, cell: Cell) -> Result<(), MoveError> {
+        if index >= 9 {
+            return Err(MoveError::OutOfRange(index));
+        }
+        self.cells[index] = cell;
+        Ok(())
+    }
+
+    /// Reads a typed cell index, checking it is on the board and empty.
+    pub fn parse_move(&self, input: &str) -> Result<usize, ParseMoveError> {
+        let idx = match input.trim().parse::<usize>() {
+            Ok(idx) => idx,
+            Err(e) if *e.kind() == core::num::IntErrorKind::PosOverflow => {
+                return Err(ParseMoveError::OutOfRange);
+            }
+            Err(_) => return Err(ParseMoveError::NotANumber),
+        };
+        if idx >= 9 {
+            return Err(ParseMoveError::OutOfRange);
+        }
+        if self.cells[idx] != Cell::Empty {
+            return Err(ParseMoveError::Occupied(idx));
+        }
+        Ok(idx)
+    }
+
+    /// A copy of the board with `player` placed at `index`, or `None` if the
+    /// index is out of range, the cell is taken or `player` is `Empty`.
+    pub fn with_move(&self, index: usize, player: Cell) -> Option<Board> {
+        if index >= 9 || self.cells[index] != Cell::Empty || player == Cell::Empty {
+            return None;
+        }
+        let mut board = self.clone();
+        board.cells[index] = player;
+        Some(board)
+    }
+
+    /// `(index, old, new)` for every cell that differs from `other`.
+    pub fn diff(&self, other: &Board) -> Vec<(usize, Cell, Cell)> {
+        (0..9)
+            .filter(|&i| self.cells[i] != other.cells[i])
+            .map(|i| (i, self.cells[i], other.cells[i]))
+            .collect()
+    }
+
+    /// Whether `next` is this board plus exactly one legal `player` mark: on a
+    /// cell that was empty, while the game was still in progress. Always
+    /// compares all nine cells, without allocating.
+    pub fn is_single_move_successor(&self, next: &Board, player: Cell) -> bool {
+        if player == Cell::Empty || self.status() != GameStatus::InProgress {
+            return false;
+        }
+        let mut placed = 0;
+        let mut invalid = false;
+        for i in 0..9 {
+            let changed = self.cells[i] != next.cells[i];
+            placed += changed as u32;
+            invalid |= changed && (self.cells[i] != Cell::Empty || next.cells[i] != player);
+        }
+        placed == 1 && !invalid
+    }
+
+    /// The board as a base-3 number: cell `i` contributes `to_u8() * 3^i`.
+    pub fn encode(&self) -> u32 {
+        self.cells
+            .iter()
+            .rev()
+            .fold(0, |acc, cell| acc * 3 + cell.to_u8() as u32)
+    }
+
+    /// The board with every mark moved through `transform`.
+    pub fn transform(&self, transform: Symmetry) -> Board {
+        let mut board = Board::new();
+        for i in 0..9 {
+            board.cells[transform_index(i, transform)] = self.cells[i];
+        }
+        board
+    }
+
+    /// The symmetric variant with the smallest `encode`, and the transform
+    /// that produced it from this board.
+    pub fn canonical(&self) -> (Board, Symmetry) {
+        Symmetry::ALL
+            .iter()
+            .map(|&sym| (self.transform(sym), sym))
+            .min_by_key(|(board, _)| board.encode())
+            .unwrap()
+    }
+
+    /// `encode` of the board under each transform, in `Symmetry::ALL` order.
+    /// Two boards are symmetric exactly when they share a minimum signature.
+    pub fn symmetry_signatures(&self) -> [u32; 8] {
+        Symmetry::ALL.map(|sym| self.transform(sym).encode())
+    }
+
+    pub fn is_canonical(&self) -> bool {
+        self.canonical().0 == *self
+    }
+
+    /// The same position with X and O swapped.
+    pub fn flip_players(&self) -> Board {
+        Board {
+            cells: self.cells.map(|c| match c {
+                Cell::X => Cell::O,
+                Cell::O => Cell::X,
+                Cell::Empty => Cell::Empty,
+            }),
+        }
+    }
+
+    pub fn rows(&self) -> [[Cell; 3]; 3] {
+        let c = &self.cells;
+        [[c[0], c[1], c[2]], [c[3], c[4], c[5]], [c[6], c[7], c[8]]]
+    }
+
+    pub fn columns(&self) -> [[Cell; 3]; 3] {
+        let c = &self.cells;
+        [[c[0], c[3], c[6]], [c[1], c[4], c[7]], [c[2], c[5], c[8]]]
+    }
+
+    pub fn diagonals(&self) -> [[Cell; 3]; 2] {
+        let c = &self.cells;
+        [[c[0], c[4], c[8]], [c[2], c[4], c[6]]]
+    }
+
+    /// Renders the board as a bordered grid, leaving empty cells blank.
+    pub fn render_ascii(&self) -> String {
+        self.render_grid(false)
+    }
+
+    /// Like `render_ascii`, but shows the index of each empty cell.
+    pub fn render_ascii_with_indices(&self) -> String {
+        self.render_grid(true)
+    }
+
+    fn render_grid(&self, show_indices: bool) -> String {
+        let mut out = String::new();
+        for row in 0..3 {
+            if row > 0 {
+                out.push_str("---+---+---\n");
+            }
+            for col in 0..3 {
+                let idx = row * 3 + col;
+                let cell = match self.cells[idx] {
+                    Cell::Empty if show_indices => format!(" {} ", idx),
+                    Cell::Empty => String::from("   "),
+                    filled => format!(" {} ", filled),
+                };
+                out.push_str(&cell);
+                if col < 2 { out.push('|'); }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The board as three lines of emoji for pasting into chat. Unlike the
+    /// other renderers there is no trailing newline.
+    pub fn to_emoji(&self) -> String {
+        let rows: Vec<String> = self
+            .rows()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Cell::X => EMOJI_X,
+                        Cell::O => EMOJI_O,
+                        Cell::Empty => EMOJI_EMPTY,
+                    })
+                    .collect()
+            })
+            .collect();
+        rows.join("\n")
+    }
+
+    /// The board with each empty cell shown as its index, e.g. `[0] [X] [2]`.
+    pub fn index_grid(&self) -> String {
+        let mut out = String::new();
+        for row in 0..3 {
+            for col in 0..3 {
+                let idx = row * 3 + col;
+                if self.cells[idx] == Cell::Empty {
+                    out.push_str(&format!("[{}]", idx));
+                } else {
+                    out.push_str(&format!("[{}]", self.cells[idx]));
+                }
+                if col < 2 { out.push(' '); }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
     pub fn winning_line(&self) -> Option<[usize; 3]> {
-        for &[a, b, c] in WIN_CONDITIONS.iter() {
+        for &[a, b, c] in WIN_LINES.iter() {
             if self.cells[a] != Cell::Empty
                 && self.cells[a] == self.cells[b]
                 && self.cells[b] == self.cells[c]
@@ -68,12 +519,154 @@ impl Board {
         None
     }
 
+    /// The completed line through `index`, e.g. the line the last move made.
+    pub fn winning_line_containing(&self, index: usize) -> Option<[usize; 3]> {
+        lines_through(index).find(|&[a, b, c]| {
+            self.cells[a] != Cell::Empty
+                && self.cells[a] == self.cells[b]
+                && self.cells[b] == self.cells[c]
+        })
+    }
+
+    /// Like `check_winner`, but only looks at the lines through `last_move`.
+    /// Only valid when every earlier position was still undecided.
+    pub fn check_winner_after(&self, last_move: usize) -> Option<Cell> {
+        self.winning_line_containing(last_move).map(|line| self.cells[line[0]])
+    }
+
+    /// The owner of the first completed line in `WIN_LINES` order. On an
+    /// impossible board where both players have a line this still reports
+    /// one of them; use `check_winner_strict` to detect that case.
     pub fn check_winner(&self) -> Option<Cell> {
-        self.winning_line().map(|line| self.cells[line[0]])
+        bitboard::BitBoard::from(self).check_winner()
+    }
+
+    pub fn check_winner_strict(&self) -> Result<Option<Cell>, BoardError> {
+        let mut winner = None;
+        for (cell, _) in self.winning_lines() {
+            match winner {
+                Some(w) if w != cell => return Err(BoardError::BothPlayersWon),
+                _ => winner = Some(cell),
+            }
+        }
+        Ok(winner)
+    }
+
+    /// Every completed line with its owner, in `WIN_LINES` order.
+    pub fn winning_lines(&self) -> Vec<(Cell, [usize; 3])> {
+        WIN_LINES
+            .iter()
+            .filter(|&&[a, b, c]| {
+                self.cells[a] != Cell::Empty
+                    && self.cells[a] == self.cells[b]
+                    && self.cells[b] == self.cells[c]
+            })
+            .map(|&line| (self.cells[line[0]], line))
+            .collect()
+    }
+
+    pub fn empty_count(&self) -> usize {
+        self.cells.iter().filter(|&&c| c == Cell::Empty).count()
     }
 
     pub fn is_full(&self) -> bool {
-        !self.cells.contains(&Cell::Empty)
+        self.empty_count() == 0
+    }
+
+    /// Empty cells where `player` would complete a line immediately. Empty
+    /// once the game is over.
+    pub fn winning_moves(&self, player: Cell) -> Vec<usize> {
+        if player == Cell::Empty || self.status() != GameStatus::InProgress {
+            return Vec::new();
+        }
+        (0..9)
+            .filter(|&i| {
+                self.with_move(i, player)
+                    .is_some_and(|board| board.check_winner() == Some(player))
+            })
+            .collect()
+    }
+
+    /// Empty cells where `player` would create two or more winning threats at
+    /// once.
+    pub fn fork_moves(&self, player: Cell) -> Vec<usize> {
+        if player == Cell::Empty {
+            return Vec::new();
+        }
+        (0..9)
+            .filter(|&i| {
+                self.with_move(i, player)
+                    .is_some_and(|board| board.winning_moves(player).len() >= 2)
+            })
+            .collect()
+    }
+
+    /// A cheap tactical check: the move that wins right now, or failing that
+    /// the move that stops the opponent winning next turn.
+    pub fn forced_move(&self, player: Cell) -> Option<usize> {
+        let opponent = match player {
+            Cell::X => Cell::O,
+            Cell::O => Cell::X,
+            _ => return None,
+        };
+
+        if let Some(&win) = self.winning_moves(player).first() {
+            return Some(win);
+        }
+        self.winning_moves(opponent).first().copied()
+    }
+
+    /// The most `player` marks in any line holding no opponent marks: 0 to 3,
+    /// where 3 means `player` has already won.
+    pub fn line_progress(&self, player: Cell) -> u32 {
+        if player == Cell::Empty {
+            return 0;
+        }
+        WIN_LINES
+            .iter()
+            .map(|line| line.map(|i| self.cells[i]))
+            .filter(|cells| cells.iter().all(|&c| c == player || c == Cell::Empty))
+            .map(|cells| cells.iter().filter(|&&c| c == player).count() as u32)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The recommended move for `player` and the strongest reason for it.
+    pub fn hint(&self, player: Cell) -> Option<(usize, HintReason)> {
+        let opponent = match player {
+            Cell::X => Cell::O,
+            Cell::O => Cell::X,
+            _ => return None,
+        };
+        if self.status() != GameStatus::InProgress {
+            return None;
+        }
+
+        if let Some(&idx) = self.winning_moves(player).first() {
+            return Some((idx, HintReason::Win));
+        }
+        if let Some(&idx) = self.winning_moves(opponent).first() {
+            return Some((idx, HintReason::Block));
+        }
+        if let Some(&idx) = self.fork_moves(player).first() {
+            return Some((idx, HintReason::Fork));
+        }
+
+        let idx = self.best_move(player)?;
+        let after = self.with_move(idx, player)?;
+        // Only a defence if it takes away the fork or forces a reply.
+        let defuses_fork = after.fork_moves(opponent).is_empty()
+            || !after.winning_moves(player).is_empty();
+        let reason = if !self.fork_moves(opponent).is_empty() && defuses_fork {
+            HintReason::BlockFork
+        } else if idx == CENTER {
+            HintReason::Center
+        } else if CORNERS.contains(&idx) {
+            HintReason::Corner
+        } else {
+            HintReason::Edge
+        };
+        Some((idx, reason))
     }
 
     fn is_block_move(&self, index: usize, player: Cell) -> bool {
@@ -83,7 +676,7 @@ impl Board {
             _ => return false,
         };
 
-        for &[a, b, c] in WIN_CONDITIONS.iter() {
+        for &[a, b, c] in WIN_LINES.iter() {
             let indices = [a, b, c];
             if indices.contains(&index) {
                 let cells = [self.cells[a], self.cells[b], self.cells[c]];
@@ -97,43 +690,199 @@ impl Board {
         false
     }
 
-    pub fn best_move(&self, player: Cell) -> Option<usize> {
-        if player != Cell::X && player != Cell::O {
-            return None;
-        }
+    /// Every legal move for `player` paired with its minimax score (1 win,
+    /// 0 draw, -1 loss), in index order. Empty once the game is over.
+    pub fn scored_moves(&self, player: Cell) -> Vec<(usize, i32)> {
+        self.depth_scored_moves(player)
+            .into_iter()
+            .map(|(i, score)| (i, score.signum()))
+            .collect()
+    }
 
+    /// Like `scored_moves`, but with `minimax`'s depth-adjusted scores, so a
+    /// quicker win or a slower loss scores higher.
+    fn depth_scored_moves(&self, player: Cell) -> Vec<(usize, i32)> {
         let opponent = match player {
             Cell::X => Cell::O,
             Cell::O => Cell::X,
-            _ => unreachable!(),
+            _ => return Vec::new(),
         };
+        if self.status() != GameStatus::InProgress {
+            return Vec::new();
+        }
+
+        let mut scores = Vec::new();
+        for i in 0..9 {
+            if let Some(board) = self.with_move(i, player) {
+                scores.push((i, board.minimax(opponent, player, 1)));
+            }
+        }
+        scores
+    }
+
+    pub fn best_move(&self, player: Cell) -> Option<usize> {
+        // Every opening move draws, so skip the full-tree search (the slowest
+        // position there is) and take the center. The full search would pick
+        // corner 0, the first of its equally scored moves, so this changes
+        // the opening but not its outcome.
+        if player != Cell::Empty && self.empty_count() == 9 {
+            return Some(CENTER);
+        }
+        if let Some(idx) = self.book_move(player) {
+            return Some(idx);
+        }
+        self.search_move(player)
+    }
 
+    /// The full minimax search behind `best_move`: the highest depth-adjusted
+    /// score, preferring a blocking move among equal ones.
+    fn search_move(&self, player: Cell) -> Option<usize> {
         let mut best_score = i32::MIN;
         let mut best_move = None;
-        for i in 0..9 {
-            if self.cells[i] == Cell::Empty {
-                let mut board = self.clone();
-                board.cells[i] = player;
-                let score = board.minimax(opponent, player);
-                if score > best_score {
-                    best_score = score;
+        for (i, score) in self.depth_scored_moves(player) {
+            if score > best_score {
+                best_score = score;
+                best_move = Some(i);
+            } else if score == best_score {
+                let block_curr = self.is_block_move(i, player);
+                if let Some(bm) = best_move
+                    && block_curr
+                    && !self.is_block_move(bm, player)
+                {
                     best_move = Some(i);
-                } else if score == best_score {
-                    let block_curr = self.is_block_move(i, player);
-                    if let Some(bm) = best_move {
-                        if block_curr && !self.is_block_move(bm, player) {
-                            best_move = Some(i);
-                        }
-                    }
                 }
             }
         }
         best_move
     }
 
-    fn minimax(&self, turn: Cell, maximizing_player: Cell) -> i32 {
+    /// The first move with the highest minimax score, without `best_move`'s
+    /// preference for blocking moves among equally scored ones.
+    pub fn best_move_raw(&self, player: Cell) -> Option<usize> {
+        let mut best: Option<(usize, i32)> = None;
+        for (i, score) in self.scored_moves(player) {
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((i, score));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// The first move with the lowest minimax score for `player`.
+    pub fn worst_move(&self, player: Cell) -> Option<usize> {
+        let mut worst: Option<(usize, i32)> = None;
+        for (i, score) in self.scored_moves(player) {
+            if worst.is_none_or(|(_, worst_score)| score < worst_score) {
+                worst = Some((i, score));
+            }
+        }
+        worst.map(|(i, _)| i)
+    }
+
+    pub fn best_move_with_difficulty(
+        &self,
+        player: Cell,
+        difficulty: Difficulty,
+        rng: &mut impl RngCore,
+    ) -> Option<usize> {
+        if player == Cell::Empty {
+            return None;
+        }
+        match difficulty {
+            Difficulty::Hard => self.best_move(player),
+            Difficulty::Medium => self
+                .forced_move(player)
+                .or_else(|| self.random_move(rng)),
+            Difficulty::Easy => self.random_move(rng),
+        }
+    }
+
+    /// Plays `best_move`, except that with probability `blunder_rate`
+    /// (clamped to `[0.0, 1.0]`) it plays a uniformly random legal move.
+    pub fn best_move_with_blunder(
+        &self,
+        player: Cell,
+        blunder_rate: f64,
+        rng: &mut impl RngCore,
+    ) -> Option<usize> {
+        if player == Cell::Empty {
+            return None;
+        }
+        if rng::gen_f64(rng) < blunder_rate.clamp(0.0, 1.0) {
+            self.random_move(rng)
+        } else {
+            self.best_move(player)
+        }
+    }
+
+    fn random_move(&self, rng: &mut impl RngCore) -> Option<usize> {
+        let empty: Vec<usize> = (0..9).filter(|&i| self.cells[i] == Cell::Empty).collect();
+        if empty.is_empty() {
+            return None;
+        }
+        Some(empty[rng::gen_index(rng, empty.len())])
+    }
+
+    /// A precomputed reply for positions with at most two marks, or `None`
+    /// past the opening. `best_move` plays this before searching.
+    pub fn book_move(&self, player: Cell) -> Option<usize> {
+        if player == Cell::Empty || 9 - self.empty_count() > 2 {
+            return None;
+        }
+        let (canonical, sym) = self.canonical();
+        let key = canonical.encode();
+        OPENING_BOOK
+            .iter()
+            .find(|&&(code, p, _)| code == key && p == player)
+            .map(|&(_, _, idx)| transform_index(idx, sym.inverse()))
+    }
+
+    /// Whether perfect play from here, with `to_move` to play, ends in a draw
+    /// for both sides. False once the game is over.
+    pub fn is_draw_inevitable(&self, to_move: Cell) -> bool {
+        if to_move == Cell::Empty || self.status() != GameStatus::InProgress {
+            return false;
+        }
+        // Zero-sum, so a draw for `to_move` is a draw for the opponent too.
+        self.minimax(to_move, to_move, 0) == 0
+    }
+
+    /// Lazily enumerates every complete game reachable from here with
+    /// `to_move` playing next. The empty board yields all 255,168 games.
+    pub fn move_tree(&self, to_move: Cell) -> MoveTree {
+        MoveTree::new(self, to_move)
+    }
+
+    /// The moves played from here when both sides follow `best_move` until
+    /// the game ends, starting with `to_move`.
+    pub fn optimal_line(&self, to_move: Cell) -> Vec<usize> {
+        let mut board = self.clone();
+        let mut turn = to_move;
+        let mut line = Vec::new();
+        while board.check_winner().is_none() && !board.is_full() {
+            let Some(i) = board.best_move(turn) else { break };
+            board.cells[i] = turn;
+            line.push(i);
+            turn = if turn == Cell::X { Cell::O } else { Cell::X };
+        }
+        line
+    }
+
+    /// Number of plies until the game ends under optimal play, or `None` if the
+    /// game is already over or `to_move` is not a player.
+    pub fn plies_to_end(&self, to_move: Cell) -> Option<u32> {
+        if to_move == Cell::Empty || self.check_winner().is_some() || self.is_full() {
+            return None;
+        }
+        Some(self.optimal_line(to_move).len() as u32)
+    }
+
+    /// Scores the position for `maximizing_player` with `turn` to move, `depth`
+    /// plies below the position being searched: a win scores `10 - depth` and
+    /// a loss `depth - 10`, so quicker wins and slower losses score higher.
+    fn minimax(&self, turn: Cell, maximizing_player: Cell, depth: i32) -> i32 {
         if let Some(winner) = self.check_winner() {
-            return if winner == maximizing_player { 1 } else { -1 };
+            return if winner == maximizing_player { 10 - depth } else { depth - 10 };
         }
         if self.is_full() {
             return 0;
@@ -148,10 +897,8 @@ impl Board {
         if turn == maximizing_player {
             let mut best = i32::MIN;
             for i in 0..9 {
-                if self.cells[i] == Cell::Empty {
-                    let mut board = self.clone();
-                    board.cells[i] = turn;
-                    let score = board.minimax(opponent, maximizing_player);
+                if let Some(board) = self.with_move(i, turn) {
+                    let score = board.minimax(opponent, maximizing_player, depth + 1);
                     best = best.max(score);
                 }
             }
@@ -159,10 +906,8 @@ impl Board {
         } else {
             let mut best = i32::MAX;
             for i in 0..9 {
-                if self.cells[i] == Cell::Empty {
-                    let mut board = self.clone();
-                    board.cells[i] = turn;
-                    let score = board.minimax(opponent, maximizing_player);
+                if let Some(board) = self.with_move(i, turn) {
+                    let score = board.minimax(opponent, maximizing_player, depth + 1);
                     best = best.min(score);
                 }
             }
@@ -170,40 +915,191 @@ impl Board {
         }
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
     pub fn get_player_move(&self) -> Option<usize> {
         println!("Enter a number (0-8) to make a move:");
-        for row in 0..3 {
-            for col in 0..3 {
-                let idx = row * 3 + col;
-                if self.cells[idx] == Cell::Empty {
-                    print!("[{}]", idx);
-                } else {
-                    print!("[{}]", self.cells[idx]);
-                }
-                if col < 2 { print!(" "); }
-            }
-            println!();
-        }
+        print!("{}", self.index_grid());
 
         print!("Your move: ");
         io::stdout().flush().expect("Failed to flush stdout");
         let mut input = String::new();
         io::stdin().read_line(&mut input).expect("Failed to read input");
 
-        match input.trim().parse::<usize>() {
-            Ok(idx) if idx < 9 && self.cells[idx] == Cell::Empty => Some(idx),
-            _ => {
-                println!("Invalid move! Please enter a number between 0 and 8 for an empty cell.");
+        match self.parse_move(&input) {
+            Ok(idx) => Some(idx),
+            Err(ParseMoveError::NotANumber) => {
+                println!("Invalid move! '{}' is not a number.", input.trim());
+                None
+            }
+            Err(ParseMoveError::Occupied(idx)) => {
+                println!("Invalid move! Cell {} is already taken.", idx);
                 None
             }
+            Err(ParseMoveError::OutOfRange) => {
+                println!("Invalid move! Please enter a number between 0 and 8.");
+                None
+            }
+        }
+    }
+}
+
+/// Best replies for every canonical position with at most two marks, as
+/// `(canonical encode, player to move, move on the canonical board)`, sorted
+/// by encode. Generated from `best_move`'s search, so the book plays exactly
+/// what the search would on each canonical board.
+const OPENING_BOOK: [(u32, Cell, usize); 32] = [
+    (0, Cell::O, 4),
+    (0, Cell::X, 4),
+    (1, Cell::O, 4),
+    (2, Cell::X, 4),
+    (3, Cell::O, 0),
+    (5, Cell::O, 3),
+    (5, Cell::X, 3),
+    (6, Cell::X, 0),
+    (7, Cell::O, 3),
+    (7, Cell::X, 3),
+    (11, Cell::O, 3),
+    (11, Cell::X, 5),
+    (33, Cell::O, 0),
+    (33, Cell::X, 0),
+    (45, Cell::O, 0),
+    (45, Cell::X, 0),
+    (63, Cell::O, 0),
+    (63, Cell::X, 0),
+    (81, Cell::O, 0),
+    (83, Cell::O, 1),
+    (83, Cell::X, 1),
+    (87, Cell::O, 0),
+    (87, Cell::X, 0),
+    (162, Cell::X, 0),
+    (163, Cell::O, 1),
+    (163, Cell::X, 1),
+    (165, Cell::O, 0),
+    (165, Cell::X, 0),
+    (297, Cell::O, 0),
+    (297, Cell::X, 0),
+    (747, Cell::O, 0),
+    (747, Cell::X, 0),
+];
+
+/// Plays `best_move` as `player` against every possible sequence of opponent
+/// replies, X moving first, and returns whether the engine never loses.
+pub fn verify_unbeatable(player: Cell) -> bool {
+    fn survives(board: &Board, turn: Cell, engine: Cell) -> bool {
+        let next = if turn == Cell::X { Cell::O } else { Cell::X };
+        match board.status() {
+            GameStatus::Won(winner) => winner == engine,
+            GameStatus::Draw => true,
+            GameStatus::InProgress if turn == engine => match board.best_move(engine) {
+                Some(idx) => board
+                    .with_move(idx, engine)
+                    .is_some_and(|b| survives(&b, next, engine)),
+                None => false,
+            },
+            GameStatus::InProgress => (0..9)
+                .filter_map(|i| board.with_move(i, turn))
+                .all(|b| survives(&b, next, engine)),
+        }
+    }
+
+    if player == Cell::Empty {
+        return false;
+    }
+    survives(&Board::new(), Cell::X, player)
+}
+
+/// The eight symmetries of the square board. Rotations are clockwise.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirror left to right.
+    FlipHorizontal,
+    /// Mirror top to bottom.
+    FlipVertical,
+    /// Mirror across the 0-4-8 diagonal.
+    FlipDiagonal,
+    /// Mirror across the 2-4-6 diagonal.
+    FlipAntiDiagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::FlipDiagonal,
+        Symmetry::FlipAntiDiagonal,
+    ];
+
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            other => other,
+        }
+    }
+}
+
+/// Maps a cell index through `transform`. Use `transform.inverse()` to map a
+/// move found on a transformed board back onto the original.
+pub fn transform_index(index: usize, transform: Symmetry) -> usize {
+    let (row, col) = (index / 3, index % 3);
+    let (row, col) = match transform {
+        Symmetry::Identity => (row, col),
+        Symmetry::Rotate90 => (col, 2 - row),
+        Symmetry::Rotate180 => (2 - row, 2 - col),
+        Symmetry::Rotate270 => (2 - col, row),
+        Symmetry::FlipHorizontal => (row, 2 - col),
+        Symmetry::FlipVertical => (2 - row, col),
+        Symmetry::FlipDiagonal => (col, row),
+        Symmetry::FlipAntiDiagonal => (2 - col, 2 - row),
+    };
+    row * 3 + col
+}
+
+/// Converts a cell index to a coordinate such as "b2". Columns are lettered
+/// a-c from left to right and rows numbered 1-3 from top to bottom, so index
+/// 0 is "a1", 2 is "c1" and 8 is "c3".
+pub fn index_to_coord(index: usize) -> Option<String> {
+    if index >= 9 {
+        return None;
+    }
+    let col = (b'a' + (index % 3) as u8) as char;
+    let row = (b'1' + (index / 3) as u8) as char;
+    Some(format!("{}{}", col, row))
+}
+
+/// Inverse of `index_to_coord`. The column letter is case-insensitive.
+pub fn coord_to_index(coord: &str) -> Option<usize> {
+    match coord.trim().as_bytes() {
+        &[col, row] => {
+            let col = match col.to_ascii_lowercase() {
+                c @ b'a'..=b'c' => (c - b'a') as usize,
+                _ => return None,
+            };
+            let row = match row {
+                r @ b'1'..=b'3' => (r - b'1') as usize,
+                _ => return None,
+            };
+            Some(row * 3 + col)
         }
+        _ => None,
     }
 }
 
+#[cfg(test)]
+extern crate std;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     #[rustfmt::skip]
@@ -251,6 +1147,19 @@ mod tests {
         assert_eq!(board.best_move(Cell::Empty), None);
     }
 
+    #[test]
+    fn test_best_move_empty_board_fast_path() {
+        let board = Board::new();
+        assert_eq!(board.best_move(Cell::X), Some(CENTER));
+        assert_eq!(board.best_move(Cell::O), Some(CENTER));
+
+        // The full search opens in the corner; the center scores the same.
+        let full = board.best_move_raw(Cell::X).unwrap();
+        assert_eq!(full, 0);
+        let scores = board.scored_moves(Cell::X);
+        assert_eq!(scores[CENTER].1, scores[full].1);
+    }
+
     #[test]
     fn test_check_winner() {
         let mut board = Board::new();
@@ -267,6 +1176,869 @@ mod tests {
         assert_eq!(board.check_winner(), None);
     }
 
+    #[test]
+    fn test_default() {
+        assert_eq!(Cell::default(), Cell::Empty);
+        assert_eq!(Board::default().cells, [Cell::Empty; 9]);
+    }
+
+    #[test]
+    fn test_board_hash() {
+        use std::collections::HashSet;
+
+        let mut a = Board::new();
+        a.cells[4] = Cell::X;
+        let mut b = Board::new();
+        b.cells[4] = Cell::X;
+
+        let mut set = HashSet::new();
+        set.insert(a);
+        set.insert(b);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_win_lines() {
+        assert_eq!(WIN_LINES.len(), 8);
+
+        let mut counts = [0; 9];
+        for line in WIN_LINES.iter() {
+            for &i in line {
+                counts[i] += 1;
+            }
+        }
+        // Corners sit on 3 lines, edges on 2 and the center on 4.
+        assert_eq!(counts, [3, 2, 3, 2, 4, 2, 3, 2, 3]);
+    }
+
+    #[test]
+    fn test_forced_move() {
+        let mut board = Board::new();
+        assert_eq!(board.forced_move(Cell::X), None);
+
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+        assert_eq!(board.winning_moves(Cell::X), vec![2]);
+        assert_eq!(board.forced_move(Cell::X), Some(2));
+
+        board.cells[1] = Cell::Empty;
+        assert_eq!(board.forced_move(Cell::X), Some(5));
+
+        board.cells = [Cell::Empty; 9];
+        board.cells[4] = Cell::X;
+        board.cells[0] = Cell::O;
+        assert_eq!(board.forced_move(Cell::X), None);
+        assert_eq!(board.forced_move(Cell::Empty), None);
+
+        // X has already won along the top row; nothing is left to win or block.
+        board.cells = [Cell::Empty; 9];
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[2] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+        assert!(board.winning_moves(Cell::X).is_empty());
+        assert!(board.winning_moves(Cell::O).is_empty());
+        assert_eq!(board.forced_move(Cell::O), None);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_rows_columns_diagonals() {
+        let mut board = Board::new();
+        board.cells = [
+            Cell::X,     Cell::O,     Cell::Empty,
+            Cell::Empty, Cell::X,     Cell::O,
+            Cell::O,     Cell::Empty, Cell::X,
+        ];
+
+        assert_eq!(board.rows(), [
+            [Cell::X, Cell::O, Cell::Empty],
+            [Cell::Empty, Cell::X, Cell::O],
+            [Cell::O, Cell::Empty, Cell::X],
+        ]);
+        assert_eq!(board.columns(), [
+            [Cell::X, Cell::Empty, Cell::O],
+            [Cell::O, Cell::X, Cell::Empty],
+            [Cell::Empty, Cell::O, Cell::X],
+        ]);
+        assert_eq!(board.diagonals(), [
+            [Cell::X, Cell::X, Cell::X],
+            [Cell::Empty, Cell::X, Cell::O],
+        ]);
+    }
+
+    #[test]
+    fn test_coords() {
+        assert_eq!(index_to_coord(0).as_deref(), Some("a1"));
+        assert_eq!(index_to_coord(2).as_deref(), Some("c1"));
+        assert_eq!(index_to_coord(8).as_deref(), Some("c3"));
+        assert_eq!(index_to_coord(9), None);
+
+        for i in 0..9 {
+            let coord = index_to_coord(i).unwrap();
+            assert_eq!(coord_to_index(&coord), Some(i));
+            assert_eq!(coord_to_index(&coord.to_uppercase()), Some(i));
+        }
+
+        assert_eq!(coord_to_index("d1"), None);
+        assert_eq!(coord_to_index("a4"), None);
+        assert_eq!(coord_to_index("a"), None);
+        assert_eq!(coord_to_index("a11"), None);
+    }
+
+    #[test]
+    fn test_transform_index() {
+        assert_eq!(transform_index(0, Symmetry::Rotate90), 2);
+        assert_eq!(transform_index(2, Symmetry::Rotate90), 8);
+        assert_eq!(transform_index(1, Symmetry::FlipVertical), 7);
+        assert_eq!(transform_index(4, Symmetry::Rotate270), 4);
+
+        for sym in Symmetry::ALL {
+            for i in 0..9 {
+                let there = transform_index(i, sym);
+                assert_eq!(transform_index(there, sym.inverse()), i, "{:?} {}", sym, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scored_moves() {
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+
+        let scores = board.scored_moves(Cell::X);
+        let indices: Vec<usize> = scores.iter().map(|&(i, _)| i).collect();
+        assert_eq!(indices, vec![2, 5, 6, 7, 8]);
+
+        let best = scores.iter().max_by_key(|&&(_, score)| score).unwrap();
+        assert_eq!(*best, (2, 1));
+        assert!(board.scored_moves(Cell::Empty).is_empty());
+
+        board.cells[2] = Cell::X;
+        assert!(board.scored_moves(Cell::O).is_empty());
+        assert_eq!(board.best_move(Cell::O), None);
+    }
+
+    #[test]
+    fn test_fork_moves() {
+        let mut board = Board::new();
+        assert!(board.fork_moves(Cell::X).is_empty());
+
+        // X holds opposite corners; 6 threatens both 0-3-6 and 6-7-8.
+        board.cells[0] = Cell::X;
+        board.cells[8] = Cell::X;
+        board.cells[4] = Cell::O;
+        board.cells[2] = Cell::O;
+        assert_eq!(board.fork_moves(Cell::X), vec![6]);
+        assert!(board.fork_moves(Cell::Empty).is_empty());
+
+        // 2 wins outright for X, so only 3 and 6 are forks.
+        board.cells = [Cell::Empty; 9];
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[4] = Cell::O;
+        board.cells[5] = Cell::O;
+        assert_eq!(board.fork_moves(Cell::X), vec![3, 6]);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_plies_to_end() {
+        let mut board = Board::new();
+        assert_eq!(board.plies_to_end(Cell::X), Some(9));
+        assert_eq!(board.plies_to_end(Cell::Empty), None);
+
+        board.cells = [
+            Cell::X, Cell::O, Cell::X,
+            Cell::X, Cell::O, Cell::O,
+            Cell::O, Cell::X, Cell::Empty,
+        ];
+        assert_eq!(board.optimal_line(Cell::X), vec![8]);
+        assert_eq!(board.plies_to_end(Cell::X), Some(1));
+
+        board.cells[8] = Cell::X;
+        assert_eq!(board.plies_to_end(Cell::O), None);
+
+        // Forking at 3 also wins, but `best_move` takes the win at 8 now.
+        board.cells = [Cell::Empty; 9];
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::X;
+        board.cells[1] = Cell::O;
+        board.cells[2] = Cell::O;
+        assert_eq!(board.best_move(Cell::X), Some(8));
+        assert_eq!(board.optimal_line(Cell::X), vec![8]);
+        assert_eq!(board.plies_to_end(Cell::X), Some(1));
+    }
+
+    #[test]
+    fn test_with_move() {
+        let board = Board::new();
+        let next = board.with_move(4, Cell::X).unwrap();
+        assert_eq!(next.cells[4], Cell::X);
+        assert_eq!(board.cells[4], Cell::Empty);
+
+        assert!(next.with_move(4, Cell::O).is_none());
+        assert!(next.with_move(9, Cell::O).is_none());
+        assert!(next.with_move(0, Cell::Empty).is_none());
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::O;
+        board.cells[8] = Cell::X;
+
+        let expected = [
+            " X |   |   ",
+            "---+---+---",
+            "   | O |   ",
+            "---+---+---",
+            "   |   | X ",
+        ];
+        assert_eq!(board.render_ascii(), expected.join("\n") + "\n");
+
+        let expected = [
+            " X | 1 | 2 ",
+            "---+---+---",
+            " 3 | O | 5 ",
+            "---+---+---",
+            " 6 | 7 | X ",
+        ];
+        assert_eq!(board.render_ascii_with_indices(), expected.join("\n") + "\n");
+    }
+
+    #[test]
+    fn test_best_move_with_difficulty() {
+        let mut rng = XorShiftRng::new(7);
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+
+        for _ in 0..50 {
+            let idx = board
+                .best_move_with_difficulty(Cell::O, Difficulty::Easy, &mut rng)
+                .unwrap();
+            assert_eq!(board.cells[idx], Cell::Empty);
+        }
+        assert_eq!(
+            board.best_move_with_difficulty(Cell::O, Difficulty::Medium, &mut rng),
+            Some(5)
+        );
+        assert_eq!(
+            board.best_move_with_difficulty(Cell::X, Difficulty::Hard, &mut rng),
+            board.best_move(Cell::X)
+        );
+        assert_eq!(
+            board.best_move_with_difficulty(Cell::Empty, Difficulty::Easy, &mut rng),
+            None
+        );
+    }
+
+    #[test]
+    fn test_best_move_with_blunder() {
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+
+        let best = board.best_move(Cell::O);
+        let mut rng = XorShiftRng::new(11);
+        for _ in 0..20 {
+            assert_eq!(board.best_move_with_blunder(Cell::O, 0.0, &mut rng), best);
+            assert_eq!(board.best_move_with_blunder(Cell::O, -3.0, &mut rng), best);
+        }
+
+        let mut a = XorShiftRng::new(11);
+        let mut b = XorShiftRng::new(11);
+        let mut picked = Vec::new();
+        for _ in 0..50 {
+            let idx = board.best_move_with_blunder(Cell::O, 1.0, &mut a).unwrap();
+            assert_eq!(board.cells[idx], Cell::Empty);
+            assert_eq!(board.best_move_with_blunder(Cell::O, 7.0, &mut b), Some(idx));
+            picked.push(idx);
+        }
+        assert!(picked.iter().any(|&idx| Some(idx) != best));
+        assert_eq!(board.best_move_with_blunder(Cell::Empty, 0.0, &mut a), None);
+    }
+
+    #[test]
+    fn test_xorshift_seed_is_reproducible() {
+        let mut a = XorShiftRng::new(42);
+        let mut b = XorShiftRng::new(42);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(XorShiftRng::new(0).next_u64(), 0);
+    }
+
+    #[test]
+    fn test_apply_move() {
+        let mut board = Board::new();
+        assert_eq!(board.status(), GameStatus::InProgress);
+        assert_eq!(board.apply_move(4, Cell::X), Ok(()));
+        assert_eq!(board.apply_move(4, Cell::O), Err(MoveError::Occupied(4)));
+        assert_eq!(board.apply_move(9, Cell::O), Err(MoveError::OutOfRange(9)));
+        assert_eq!(board.apply_move(0, Cell::Empty), Err(MoveError::InvalidPlayer));
+
+        board.cells[3] = Cell::X;
+        board.cells[5] = Cell::X;
+        assert_eq!(board.status(), GameStatus::Won(Cell::X));
+        let before = board.clone();
+        assert_eq!(board.apply_move(0, Cell::O), Err(MoveError::GameOver));
+        assert!(board == before);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_status_draw() {
+        let mut board = Board::new();
+        board.cells = [
+            Cell::X, Cell::O, Cell::X,
+            Cell::X, Cell::O, Cell::O,
+            Cell::O, Cell::X, Cell::X,
+        ];
+        assert_eq!(board.status(), GameStatus::Draw);
+        assert_eq!(board.apply_move(0, Cell::O), Err(MoveError::GameOver));
+    }
+
+    #[test]
+    fn test_to_emoji() {
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::O;
+        board.cells[8] = Cell::X;
+
+        let emoji = board.to_emoji();
+        assert_eq!(emoji, "❌⬜⬜\n⬜⭕⬜\n⬜⬜❌");
+        assert_eq!(emoji.lines().count(), 3);
+        assert!(emoji.lines().all(|line| line.chars().count() == 3));
+    }
+
+    #[test]
+    fn test_index_grid() {
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::O;
+        board.cells[7] = Cell::X;
+        assert_eq!(board.index_grid(), "[X] [1] [2]\n[3] [O] [5]\n[6] [X] [8]\n");
+    }
+
+    #[test]
+    fn test_check_winner_strict() {
+        let mut board = Board::new();
+        assert_eq!(board.check_winner_strict(), Ok(None));
+
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[2] = Cell::X;
+        assert_eq!(board.check_winner_strict(), Ok(Some(Cell::X)));
+
+        board.cells[6] = Cell::O;
+        board.cells[7] = Cell::O;
+        board.cells[8] = Cell::O;
+        assert_eq!(board.check_winner_strict(), Err(BoardError::BothPlayersWon));
+        assert_eq!(board.check_winner(), Some(Cell::X));
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut board = Board::new();
+        board.cells[4] = Cell::X;
+        assert!(board.diff(&board.clone()).is_empty());
+
+        let next = board.with_move(0, Cell::O).unwrap();
+        assert_eq!(board.diff(&next), vec![(0, Cell::Empty, Cell::O)]);
+    }
+
+    #[test]
+    fn test_is_single_move_successor() {
+        let mut board = Board::new();
+        board.cells[4] = Cell::X;
+
+        let next = board.with_move(0, Cell::O).unwrap();
+        assert!(board.is_single_move_successor(&next, Cell::O));
+        assert!(!board.is_single_move_successor(&next, Cell::X));
+        assert!(!board.is_single_move_successor(&board, Cell::O));
+
+        let two = next.with_move(8, Cell::O).unwrap();
+        assert!(!board.is_single_move_successor(&two, Cell::O));
+
+        let mut changed = next.clone();
+        changed.cells[4] = Cell::O;
+        assert!(!next.is_single_move_successor(&changed, Cell::O));
+
+        // No move is legal once X has completed the top row.
+        let mut won = Board::new();
+        won.cells[0] = Cell::X;
+        won.cells[1] = Cell::X;
+        won.cells[2] = Cell::X;
+        won.cells[4] = Cell::O;
+        won.cells[5] = Cell::O;
+        let after = won.with_move(3, Cell::O).unwrap();
+        assert!(!won.is_single_move_successor(&after, Cell::O));
+    }
+
+    #[test]
+    fn test_cell_u8() {
+        for v in 0..3 {
+            assert_eq!(Cell::from_u8(v).unwrap().to_u8(), v);
+        }
+        assert_eq!(Cell::from_u8(1), Some(Cell::X));
+        assert_eq!(Cell::from_u8(3), None);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let mut board = Board::new();
+        board.cells[8] = Cell::X;
+        let (canonical, sym) = board.canonical();
+        assert_eq!(canonical.encode(), 1);
+        assert!(canonical.transform(sym.inverse()) == board);
+
+        let mut rotated = Board::new();
+        rotated.cells[2] = Cell::X;
+        assert_eq!(rotated.canonical().0, canonical);
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        let mut board = Board::new();
+        board.cells[2] = Cell::X;
+        board.cells[3] = Cell::O;
+
+        let signatures = board.symmetry_signatures();
+        assert_eq!(signatures[0], board.encode());
+
+        let mut class: Vec<Board> = Symmetry::ALL.iter().map(|&s| board.transform(s)).collect();
+        class.sort_by_key(Board::encode);
+        class.dedup();
+        assert_eq!(class.len(), 8);
+
+        let canonical: Vec<&Board> = class.iter().filter(|b| b.is_canonical()).collect();
+        assert_eq!(canonical.len(), 1);
+        assert_eq!(canonical[0].encode(), *signatures.iter().min().unwrap());
+        assert!(Board::new().is_canonical());
+    }
+
+    #[test]
+    fn test_book_move() {
+        let best_score = |board: &Board, player: Cell| {
+            board.scored_moves(player).iter().map(|&(_, s)| s).max().unwrap()
+        };
+        let score_of = |board: &Board, player: Cell, idx: usize| {
+            board.scored_moves(player).iter().find(|&&(i, _)| i == idx).unwrap().1
+        };
+
+        let board = Board::new();
+        let m = board.book_move(Cell::X).unwrap();
+        assert_eq!(score_of(&board, Cell::X, m), best_score(&board, Cell::X));
+
+        for i in 0..9 {
+            let mut board = Board::new();
+            board.cells[i] = Cell::X;
+            let m = board.book_move(Cell::O).unwrap();
+            assert_eq!(board.cells[m], Cell::Empty);
+            assert_eq!(score_of(&board, Cell::O, m), best_score(&board, Cell::O), "X at {}", i);
+        }
+
+        for i in 0..9 {
+            for j in (0..9).filter(|&j| j != i) {
+                let mut board = Board::new();
+                board.cells[i] = Cell::X;
+                board.cells[j] = Cell::O;
+                for player in [Cell::X, Cell::O] {
+                    let m = board.book_move(player).unwrap();
+                    assert_eq!(board.cells[m], Cell::Empty);
+                    assert_eq!(score_of(&board, player, m), best_score(&board, player));
+                }
+            }
+        }
+
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::O;
+        board.cells[8] = Cell::X;
+        assert_eq!(board.book_move(Cell::O), None);
+        assert_eq!(Board::new().book_move(Cell::Empty), None);
+    }
+
+    #[test]
+    fn test_best_move_uses_book() {
+        let mut covered = vec![(Board::new(), Cell::X), (Board::new(), Cell::O)];
+        for i in 0..9 {
+            let mut board = Board::new();
+            board.cells[i] = Cell::X;
+            covered.push((board.flip_players(), Cell::X));
+            covered.push((board.clone(), Cell::O));
+            for j in (0..9).filter(|&j| j != i) {
+                let mut board = board.clone();
+                board.cells[j] = Cell::O;
+                covered.push((board.clone(), Cell::X));
+                covered.push((board, Cell::O));
+            }
+        }
+
+        for (board, player) in covered {
+            let book = board.book_move(player);
+            assert!(book.is_some());
+            assert_eq!(board.best_move(player), book);
+            if board.is_canonical() && board.empty_count() < 9 {
+                assert_eq!(board.search_move(player), book);
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_moves() {
+        let mut board = Board::new();
+        assert_eq!(board.apply_moves(&[(4, Cell::X), (0, Cell::O)]), Ok(()));
+        assert_eq!(board.cells[4], Cell::X);
+        assert_eq!(board.cells[0], Cell::O);
+
+        let before = board.clone();
+        assert_eq!(
+            board.apply_moves(&[(8, Cell::X), (2, Cell::O), (4, Cell::X)]),
+            Err((2, MoveError::Occupied(4)))
+        );
+        assert_eq!(board, before);
+    }
+
+    #[test]
+    fn test_clear_and_set_cell() {
+        let mut board = Board::new();
+        assert_eq!(board.set_cell(4, Cell::O), Ok(()));
+        assert_eq!(board.clear_cell(4), Ok(Cell::O));
+        assert_eq!(board.cells[4], Cell::Empty);
+        assert_eq!(board.clear_cell(4), Ok(Cell::Empty));
+
+        assert_eq!(board.clear_cell(9), Err(MoveError::OutOfRange(9)));
+        assert_eq!(board.set_cell(9, Cell::X), Err(MoveError::OutOfRange(9)));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_empty_count() {
+        let mut board = Board::new();
+        assert_eq!(board.empty_count(), 9);
+
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::O;
+        assert_eq!(board.empty_count(), 7);
+        assert!(!board.is_full());
+
+        board.cells = [
+            Cell::X, Cell::O, Cell::X,
+            Cell::X, Cell::O, Cell::O,
+            Cell::O, Cell::X, Cell::X,
+        ];
+        assert_eq!(board.empty_count(), 0);
+        assert!(board.is_full());
+    }
+
+    #[test]
+    fn test_winning_line_containing() {
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+        board.cells[8] = Cell::O;
+        assert_eq!(board.winning_line_containing(8), None);
+
+        board.cells[2] = Cell::X;
+        assert_eq!(board.winning_line_containing(2), Some([0, 1, 2]));
+        assert_eq!(board.winning_line_containing(4), None);
+
+        // winning_line reports the row first; the line through 6 is the
+        // diagonal the last move made.
+        board.cells[4] = Cell::X;
+        board.cells[6] = Cell::X;
+        assert_eq!(board.winning_line(), Some([0, 1, 2]));
+        assert_eq!(board.winning_line_containing(6), Some([2, 4, 6]));
+        assert_eq!(board.winning_line_containing(9), None);
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let board = Board::try_from(&[1u8, 0, 0, 0, 2, 0, 0, 0, 1][..]).unwrap();
+        assert_eq!(board.cells[0], Cell::X);
+        assert_eq!(board.cells[4], Cell::O);
+        assert_eq!(board.cells[8], Cell::X);
+        assert_eq!(board.empty_count(), 6);
+
+        assert_eq!(
+            Board::try_from(&[0u8; 8][..]),
+            Err(ParseBoardError::WrongLength(8))
+        );
+        assert_eq!(
+            Board::try_from(&[0u8, 0, 0, 3, 0, 0, 0, 0, 0][..]),
+            Err(ParseBoardError::InvalidValue { index: 3, value: 3 })
+        );
+    }
+
+    #[test]
+    fn test_line_progress() {
+        let mut board = Board::new();
+        assert_eq!(board.line_progress(Cell::X), 0);
+
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        assert_eq!(board.line_progress(Cell::X), 2);
+
+        // Blocking the row leaves the best open line with one X.
+        board.cells[2] = Cell::O;
+        assert_eq!(board.line_progress(Cell::X), 1);
+        assert_eq!(board.line_progress(Cell::O), 1);
+        assert_eq!(board.line_progress(Cell::Empty), 0);
+    }
+
+    #[test]
+    fn test_best_move_raw() {
+        // O has a double threat at 5 and 7, so every X move loses. The raw
+        // search keeps the first move, best_move prefers a block.
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[8] = Cell::X;
+        board.cells[1] = Cell::O;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+
+        let scores = board.scored_moves(Cell::X);
+        assert!(scores.iter().all(|&(_, s)| s == -1));
+        assert_eq!(board.best_move_raw(Cell::X), Some(2));
+        assert_eq!(board.best_move(Cell::X), Some(5));
+
+        assert_eq!(board.best_move_raw(Cell::Empty), None);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_is_draw_inevitable() {
+        let mut board = Board::new();
+        board.cells = [
+            Cell::X, Cell::O, Cell::X,
+            Cell::X, Cell::O, Cell::O,
+            Cell::O, Cell::X, Cell::Empty,
+        ];
+        assert!(board.is_draw_inevitable(Cell::X));
+
+        // X to move can complete 0-3-6.
+        board.cells[6] = Cell::Empty;
+        board.cells[7] = Cell::Empty;
+        board.cells[8] = Cell::O;
+        assert!(!board.is_draw_inevitable(Cell::X));
+        assert!(!board.is_draw_inevitable(Cell::Empty));
+
+        board.cells = [
+            Cell::X, Cell::O, Cell::X,
+            Cell::X, Cell::O, Cell::O,
+            Cell::O, Cell::X, Cell::X,
+        ];
+        assert_eq!(board.status(), GameStatus::Draw);
+        assert!(!board.is_draw_inevitable(Cell::O));
+    }
+
+    #[test]
+    fn test_winning_lines() {
+        let mut board = Board::new();
+        assert!(board.winning_lines().is_empty());
+
+        for i in [0, 1, 2, 4, 8] {
+            board.cells[i] = Cell::X;
+        }
+        assert_eq!(
+            board.winning_lines(),
+            vec![(Cell::X, [0, 1, 2]), (Cell::X, [0, 4, 8])]
+        );
+    }
+
+    #[test]
+    fn test_worst_move() {
+        // X can win at 2, but anything other than 2 or 5 lets O win at 5.
+        let mut board = Board::new();
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+
+        assert_eq!(board.best_move(Cell::X), Some(2));
+        assert_eq!(board.worst_move(Cell::X), Some(6));
+        assert_eq!(board.worst_move(Cell::Empty), None);
+    }
+
+    #[test]
+    fn test_error_display() {
+        use alloc::string::ToString;
+
+        assert_eq!(MoveError::Occupied(5).to_string(), "cell 5 is already occupied");
+        assert_eq!(
+            MoveError::OutOfRange(9).to_string(),
+            "cell 9 is out of range (expected 0-8)"
+        );
+        assert_eq!(
+            MoveError::InvalidPlayer.to_string(),
+            "expected X or O, got an empty cell"
+        );
+        assert_eq!(MoveError::GameOver.to_string(), "the game is already over");
+        assert_eq!(
+            ParseMoveError::NotANumber.to_string(),
+            "expected a cell number from 0 to 8"
+        );
+        assert_eq!(
+            ParseMoveError::OutOfRange.to_string(),
+            "cell number is out of range (expected 0-8)"
+        );
+        assert_eq!(
+            ParseMoveError::Occupied(4).to_string(),
+            "cell 4 is already occupied"
+        );
+        assert_eq!(
+            BoardError::BothPlayersWon.to_string(),
+            "both X and O have a completed line"
+        );
+        assert_eq!(ParseBoardError::WrongLength(8).to_string(), "expected 9 cells, got 8");
+        assert_eq!(
+            ParseBoardError::InvalidValue { index: 3, value: 7 }.to_string(),
+            "invalid value 7 at cell 3 (expected 0, 1 or 2)"
+        );
+    }
+
+    #[test]
+    fn test_errors_are_send_sync() {
+        fn assert_error<E: core::error::Error + Send + Sync + 'static>() {}
+        assert_error::<MoveError>();
+        assert_error::<BoardError>();
+        assert_error::<ParseBoardError>();
+        assert_error::<ParseMoveError>();
+        assert_error::<ReplayError>();
+    }
+
+    #[test]
+    fn test_lines_through() {
+        assert_eq!(lines_through(4).count(), 4);
+        assert_eq!(
+            lines_through(1).collect::<Vec<_>>(),
+            vec![[0, 1, 2], [1, 4, 7]]
+        );
+        assert_eq!(lines_through(9).count(), 0);
+    }
+
+    #[test]
+    fn test_check_winner_after() {
+        for moves in ["4 0 8 2 1 7 6 3 5", "0 3 1 4 2", "4 0 2 6 3 5 1 7 8"] {
+            let mut board = Board::new();
+            let mut turn = Cell::X;
+            for idx in moves.split_whitespace().map(|m| m.parse().unwrap()) {
+                board.apply_move(idx, turn).unwrap();
+                assert_eq!(board.check_winner_after(idx), board.check_winner(), "{}", moves);
+                turn = if turn == Cell::X { Cell::O } else { Cell::X };
+            }
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_hint() {
+        let mut board = Board::new();
+        assert_eq!(board.hint(Cell::X), Some((4, HintReason::Center)));
+        assert_eq!(board.hint(Cell::Empty), None);
+
+        board.cells[0] = Cell::X;
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::O;
+        board.cells[4] = Cell::O;
+        assert_eq!(board.hint(Cell::X), Some((2, HintReason::Win)));
+
+        board.cells[1] = Cell::Empty;
+        assert_eq!(board.hint(Cell::X), Some((5, HintReason::Block)));
+
+        // X on two edges can fork at 0 (0-1-2 and 0-3-6); O has to defuse it.
+        board.cells = [Cell::Empty; 9];
+        board.cells[1] = Cell::X;
+        board.cells[3] = Cell::X;
+        board.cells[8] = Cell::O;
+        assert_eq!(board.hint(Cell::X), Some((0, HintReason::Fork)));
+        let (idx, reason) = board.hint(Cell::O).unwrap();
+        assert_eq!(reason, HintReason::BlockFork);
+        assert_eq!(Some(idx), board.best_move(Cell::O));
+
+        // O's 3 leaves X forks at 4, 6 and 8 and threatens nothing, so it is
+        // no defence.
+        board.cells = [
+            Cell::X, Cell::O, Cell::O,
+            Cell::Empty, Cell::Empty, Cell::X,
+            Cell::Empty, Cell::X, Cell::Empty,
+        ];
+        assert_eq!(board.fork_moves(Cell::X), vec![3, 4, 6, 8]);
+        assert_eq!(board.hint(Cell::O), Some((3, HintReason::Edge)));
+
+        board.cells = [Cell::Empty; 9];
+        board.cells[4] = Cell::X;
+        assert_eq!(board.hint(Cell::O), Some((0, HintReason::Corner)));
+
+        board.cells[0] = Cell::X;
+        board.cells[4] = Cell::O;
+        assert_eq!(board.hint(Cell::X), Some((1, HintReason::Edge)));
+    }
+
+    #[test]
+    fn test_parse_move() {
+        let mut board = Board::new();
+        board.cells[4] = Cell::X;
+
+        assert_eq!(board.parse_move(" 3\n"), Ok(3));
+        assert_eq!(board.parse_move("four"), Err(ParseMoveError::NotANumber));
+        assert_eq!(board.parse_move(""), Err(ParseMoveError::NotANumber));
+        assert_eq!(board.parse_move("-1"), Err(ParseMoveError::NotANumber));
+        assert_eq!(board.parse_move("9"), Err(ParseMoveError::OutOfRange));
+        assert_eq!(
+            board.parse_move("99999999999999999999"),
+            Err(ParseMoveError::OutOfRange)
+        );
+        assert_eq!(board.parse_move("4"), Err(ParseMoveError::Occupied(4)));
+    }
+
+    #[test]
+    fn test_flip_players() {
+        let mut board = Board::new();
+        for i in [0, 1, 2] {
+            board.cells[i] = Cell::X;
+        }
+        board.cells[4] = Cell::O;
+
+        let flipped = board.flip_players();
+        assert_eq!(flipped.check_winner(), Some(Cell::O));
+        assert_eq!(flipped.cells[4], Cell::X);
+        assert_eq!(flipped.cells[8], Cell::Empty);
+        assert_eq!(flipped.flip_players(), board);
+    }
+
+    #[test]
+    fn test_verify_unbeatable() {
+        assert!(verify_unbeatable(Cell::X));
+        assert!(verify_unbeatable(Cell::O));
+        assert!(!verify_unbeatable(Cell::Empty));
+    }
+
+    #[test]
+    fn test_center_corners_edges() {
+        let mut seen = [0; 9];
+        for i in CORNERS.iter().chain(EDGES.iter()).chain(core::iter::once(&CENTER)) {
+            seen[*i] += 1;
+        }
+        assert_eq!(seen, [1; 9]);
+    }
+
     #[test]
     fn test_winning_line() {
         let mut board = Board::new();
@@ -1,10 +1,10 @@
-use tic_tac_toe::Board;
-use tic_tac_toe::Cell;
+use tic_tac_toe::{step, Cell, Game, GameStatus};
 use std::io;
 use std::io::Write;
 
 fn main() {
-    let mut board = Board::new();
+    let analyze = std::env::args().skip(1).any(|arg| arg == "--analyze");
+
     println!("Welcome to Tic-Tac-Toe!");
     println!("Do you want to play first (as X)? (y/n)");
     print!("Choice: ");
@@ -12,67 +12,41 @@ fn main() {
     let mut input = String::new();
     io::stdin().read_line(&mut input).expect("Failed to read input");
 
-    let (player, _computer) = if input.trim().to_lowercase().starts_with('y') {
-        (Cell::X, Cell::O)
+    let player = if input.trim().to_lowercase().starts_with('y') {
+        Cell::X
     } else {
-        (Cell::O, Cell::X)
+        Cell::O
     };
 
+    let mut game = Game::new(Cell::X);
+    game.set_human(player);
+
     loop {
         println!("\nCurrent board:");
-        board.print();
+        game.board.print();
 
-        if player == Cell::X {
-            loop {
-                match board.get_player_move() {
-                    Some(idx) => { board.cells[idx] = Cell::X; break; }
-                    None => continue,
-                }
+        let before = game.board.clone();
+        let status = step(&mut game, |board| loop {
+            if let Some(idx) = board.get_player_move() {
+                break idx;
+            }
+        });
+        for (idx, _, cell) in before.diff(&game.board) {
+            if cell != player {
+                println!("Computer plays {} at {}:", cell, idx);
             }
-        } else if let Some(idx) = board.best_move(Cell::X) {
-            println!("Computer plays X at {}:", idx);
-            board.cells[idx] = Cell::X;
-        }
-
-        if let Some(winner) = board.check_winner() {
-            println!("\nFinal board:");
-            board.print();
-            println!("{}", if winner == player { "You win!" } else { "You lose!" });
-            break;
         }
-        if board.is_full() {
-            println!("\nFinal board:");
-            board.print();
-            println!("Draw!");
-            break;
+        if analyze {
+            print!("{}", game.analysis());
         }
 
-        println!("\nCurrent board:");
-        board.print();
-
-        if player == Cell::O {
-            loop {
-                match board.get_player_move() {
-                    Some(idx) => { board.cells[idx] = Cell::O; break; }
-                    None => continue,
-                }
-            }
-        } else if let Some(idx) = board.best_move(Cell::O) {
-            println!("Computer plays O at {}:", idx);
-            board.cells[idx] = Cell::O;
+        if status == GameStatus::InProgress {
+            continue;
         }
 
-        if let Some(winner) = board.check_winner() {
-            println!("\nFinal board:");
-            board.print();
-            println!("{}", if winner == player { "You win!" } else { "You lose!" });
-            break;
-        }
-        if board.is_full() {
-            println!("\nFinal board:");
-            board.print();
-            println!("Draw!");
-            break;
-        }
+        println!("\nFinal board:");
+        game.board.print();
+        println!("{}", game.result_message(player));
+        break;
     }
 }
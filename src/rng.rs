@@ -0,0 +1,41 @@
+/// Source of randomness for the non-perfect AI levels. Implement this for
+/// your own generator, or use `XorShiftRng` for a small seedable default.
+pub trait RngCore {
+    fn next_u64(&mut self) -> u64;
+}
+
+/// A xorshift64* generator. Not cryptographic, but fast, `no_std` and
+/// reproducible for a given seed.
+#[derive(Clone, Debug)]
+pub struct XorShiftRng {
+    state: u64,
+}
+
+impl XorShiftRng {
+    pub fn new(seed: u64) -> XorShiftRng {
+        // An all-zero state would only ever produce zeros.
+        let state = if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed };
+        XorShiftRng { state }
+    }
+}
+
+impl RngCore for XorShiftRng {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+/// Picks an index in `0..len`. `len` must be non-zero.
+pub(crate) fn gen_index(rng: &mut impl RngCore, len: usize) -> usize {
+    (rng.next_u64() % len as u64) as usize
+}
+
+/// A uniform float in `[0, 1)`.
+pub(crate) fn gen_f64(rng: &mut impl RngCore) -> f64 {
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+
+use crate::{Board, Cell, GameStatus};
+
+struct Frame {
+    board: Board,
+    turn: Cell,
+    /// The next cell index to try from this position.
+    next: usize,
+}
+
+/// Depth-first walk over every complete game from a position, built by
+/// `Board::move_tree`. Only the current path is kept in memory.
+pub struct MoveTree {
+    stack: Vec<Frame>,
+    path: Vec<usize>,
+    /// Set when the starting position is already over.
+    finished: Option<GameStatus>,
+}
+
+impl MoveTree {
+    pub(crate) fn new(board: &Board, to_move: Cell) -> MoveTree {
+        let mut tree = MoveTree { stack: Vec::new(), path: Vec::new(), finished: None };
+        match board.status() {
+            GameStatus::InProgress if to_move != Cell::Empty => {
+                tree.stack.push(Frame { board: board.clone(), turn: to_move, next: 0 });
+            }
+            GameStatus::InProgress => {}
+            status => tree.finished = Some(status),
+        }
+        tree
+    }
+}
+
+impl Iterator for MoveTree {
+    /// The moves of one complete game and how it ended.
+    type Item = (Vec<usize>, GameStatus);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(status) = self.finished.take() {
+            return Some((Vec::new(), status));
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(idx) = (frame.next..9).find(|&i| frame.board.cells[i] == Cell::Empty) else {
+                self.stack.pop();
+                self.path.pop();
+                continue;
+            };
+            frame.next = idx + 1;
+
+            let turn = frame.turn;
+            let child = frame.board.with_move(idx, turn).expect("cell was empty");
+            self.path.push(idx);
+            match child.status() {
+                GameStatus::InProgress => {
+                    let next = if turn == Cell::X { Cell::O } else { Cell::X };
+                    self.stack.push(Frame { board: child, turn: next, next: 0 });
+                }
+                status => {
+                    let game = self.path.clone();
+                    self.path.pop();
+                    return Some((game, status));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_counts_all_games() {
+        let mut games = 0;
+        let (mut x_wins, mut o_wins, mut draws) = (0, 0, 0);
+        for (moves, status) in Board::new().move_tree(Cell::X) {
+            games += 1;
+            assert!((5..=9).contains(&moves.len()));
+            match status {
+                GameStatus::Won(Cell::X) => x_wins += 1,
+                GameStatus::Won(_) => o_wins += 1,
+                GameStatus::Draw => draws += 1,
+                GameStatus::InProgress => unreachable!(),
+            }
+        }
+        assert_eq!(games, 255_168);
+        assert_eq!((x_wins, o_wins, draws), (131_184, 77_904, 46_080));
+    }
+
+    #[test]
+    fn test_near_end_and_terminal() {
+        let mut board = Board::new();
+        for (i, cell) in [Cell::X, Cell::O, Cell::X, Cell::X, Cell::O, Cell::O, Cell::O, Cell::X]
+            .into_iter()
+            .enumerate()
+        {
+            board.cells[i] = cell;
+        }
+        let games: Vec<_> = board.move_tree(Cell::X).collect();
+        assert_eq!(games, vec![(vec![8], GameStatus::Draw)]);
+
+        board.cells[8] = Cell::X;
+        let games: Vec<_> = board.move_tree(Cell::O).collect();
+        assert_eq!(games, vec![(vec![], GameStatus::Draw)]);
+        assert_eq!(Board::new().move_tree(Cell::Empty).count(), 0);
+    }
+}
@@ -1,63 +1,140 @@
 use wasm_bindgen::prelude::*;
-use tic_tac_toe::{Board, Cell};
+use tic_tac_toe::{replay, Board, Cell, Difficulty, XorShiftRng};
+
+const DEFAULT_SEED: u64 = 0x5EED;
+
+/// Score reported by `scored_moves` for cells that cannot be played.
+const NO_SCORE: i32 = i32::MIN;
 
 #[wasm_bindgen]
 pub struct WasmBoard {
     board: Board,
+    rng: XorShiftRng,
+    /// Moves made through `make_move`, oldest first.
+    history: Vec<usize>,
+}
+
+/// Like `Cell::from_u8`, but only accepts 1 (X) and 2 (O).
+fn player_from_u8(v: u8) -> Option<Cell> {
+    Cell::from_u8(v).filter(|&c| c != Cell::Empty)
+}
+
+impl WasmBoard {
+    fn from_board(board: Board, history: Vec<usize>) -> WasmBoard {
+        WasmBoard { board, rng: XorShiftRng::new(DEFAULT_SEED), history }
+    }
 }
 
 #[wasm_bindgen]
 impl WasmBoard {
     #[wasm_bindgen(constructor)]
     pub fn new() -> WasmBoard {
-        WasmBoard { board: Board::new() }
+        WasmBoard::from_board(Board::new(), Vec::new())
+    }
+
+    pub fn from_cells(cells: &[u8]) -> Option<WasmBoard> {
+        let board = Board::try_from(cells).ok()?;
+        Some(WasmBoard::from_board(board, Vec::new()))
+    }
+
+    /// Rebuilds a game from a `to_move_list` string. `first_player` is 1 (X)
+    /// or 2 (O).
+    pub fn replay(moves: &str, first_player: u8) -> Option<WasmBoard> {
+        let first = player_from_u8(first_player)?;
+        let (board, _) = replay(moves, first).ok()?;
+        let history = moves
+            .split_whitespace()
+            .map(|m| m.parse().ok())
+            .collect::<Option<Vec<usize>>>()?;
+        Some(WasmBoard::from_board(board, history))
+    }
+
+    /// Space-separated indices of the moves made so far. Boards restored with
+    /// `from_cells` have no history before the restore.
+    pub fn to_move_list(&self) -> String {
+        self.history
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
     pub fn reset(&mut self) {
         self.board = Board::new();
+        self.history.clear();
     }
 
     pub fn get_cells(&self) -> Vec<u8> {
         self.board
             .cells
             .iter()
-            .map(|c| match c {
-                Cell::Empty => 0u8,
-                Cell::X => 1u8,
-                Cell::O => 2u8,
-            })
+            .map(|c| c.to_u8())
             .collect()
     }
 
+    /// Returns false, leaving the board untouched, if the move is illegal or
+    /// the game is already over.
     pub fn make_move(&mut self, index: usize, player: u8) -> bool {
-        if index >= 9 || self.board.cells[index] != Cell::Empty {
+        let Some(cell) = player_from_u8(player) else {
             return false;
-        }
-        self.board.cells[index] = match player {
-            1 => Cell::X,
-            2 => Cell::O,
-            _ => return false,
         };
+        if self.board.apply_move(index, cell).is_err() {
+            return false;
+        }
+        self.history.push(index);
         true
     }
 
     pub fn best_move(&self, player: u8) -> Option<usize> {
-        let cell = match player {
-            1 => Cell::X,
-            2 => Cell::O,
+        self.board.best_move(player_from_u8(player)?)
+    }
+
+    /// One minimax score per cell (1 win, 0 draw, -1 loss) for `player`, with
+    /// `i32::MIN` for occupied cells and for every cell once the game is over.
+    /// Empty for an invalid player.
+    pub fn scored_moves(&self, player: u8) -> Vec<i32> {
+        let Some(cell) = player_from_u8(player) else {
+            return Vec::new();
+        };
+        let mut scores = vec![NO_SCORE; 9];
+        for (i, score) in self.board.scored_moves(cell) {
+            scores[i] = score;
+        }
+        scores
+    }
+
+    /// Reseeds the generator used by the Easy and Medium levels. The same seed
+    /// replays the same moves; seed from `Date.now()` for variety.
+    pub fn set_seed(&mut self, seed: u32) {
+        self.rng = XorShiftRng::new(seed as u64);
+    }
+
+    /// `difficulty` is 0 (Easy), 1 (Medium) or 2 (Hard).
+    pub fn best_move_with_difficulty(&mut self, player: u8, difficulty: u8) -> Option<usize> {
+        let cell = player_from_u8(player)?;
+        let difficulty = match difficulty {
+            0 => Difficulty::Easy,
+            1 => Difficulty::Medium,
+            2 => Difficulty::Hard,
             _ => return None,
         };
-        self.board.best_move(cell)
+        self.board.best_move_with_difficulty(cell, difficulty, &mut self.rng)
     }
 
     pub fn check_winner(&self) -> Option<u8> {
         self.board
             .check_winner()
-            .map(|c| match c {
-                Cell::X => 1u8,
-                Cell::O => 2u8,
-                Cell::Empty => 0u8,
-            })
+            .map(Cell::to_u8)
+    }
+
+    /// Every completed line, flattened: each group of three consecutive
+    /// values is one line's cell indices, so the length is a multiple of 3.
+    pub fn winning_lines(&self) -> Vec<u32> {
+        self.board
+            .winning_lines()
+            .iter()
+            .flat_map(|(_, line)| line.map(|i| i as u32))
+            .collect()
     }
 
     pub fn winning_line(&self) -> Option<Box<[u32]>> {
@@ -66,3 +143,97 @@ impl WasmBoard {
             .map(|line| line.iter().map(|&i| i as u32).collect::<Vec<u32>>().into_boxed_slice())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_cells() {
+        let mut board = WasmBoard::new();
+        board.make_move(4, 1);
+        board.make_move(0, 2);
+
+        let cells = board.get_cells();
+        let restored = WasmBoard::from_cells(&cells).unwrap();
+        assert_eq!(restored.get_cells(), cells);
+
+        assert!(WasmBoard::from_cells(&cells[..8]).is_none());
+        assert!(WasmBoard::from_cells(&[0, 0, 0, 0, 3, 0, 0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn test_move_list_round_trip() {
+        let mut board = WasmBoard::new();
+        assert!(board.make_move(4, 1));
+        assert!(board.make_move(0, 2));
+        assert!(!board.make_move(0, 1));
+        assert!(board.make_move(8, 1));
+        assert_eq!(board.to_move_list(), "4 0 8");
+
+        let restored = WasmBoard::replay(&board.to_move_list(), 1).unwrap();
+        assert_eq!(restored.get_cells(), board.get_cells());
+        assert_eq!(restored.to_move_list(), "4 0 8");
+
+        assert!(WasmBoard::replay("4 4", 1).is_none());
+        assert!(WasmBoard::replay("4", 0).is_none());
+    }
+
+    #[test]
+    fn test_scored_moves() {
+        let mut board = WasmBoard::new();
+        board.make_move(0, 1);
+        board.make_move(4, 2);
+        board.make_move(1, 1);
+
+        let scores = board.scored_moves(2);
+        assert_eq!(scores.len(), 9);
+        for idx in [0, 1, 4] {
+            assert_eq!(scores[idx], NO_SCORE);
+        }
+        // O must block at 2.
+        assert_eq!(scores[2], 0);
+        assert_eq!(scores[3], -1);
+        assert!(board.scored_moves(0).is_empty());
+
+        // After X wins along the top row no cell is playable.
+        let won = WasmBoard::from_cells(&[1, 1, 1, 2, 2, 0, 0, 0, 0]).unwrap();
+        assert_eq!(won.scored_moves(2), vec![NO_SCORE; 9]);
+    }
+
+    #[test]
+    fn test_winning_lines() {
+        let board = WasmBoard::from_cells(&[1, 1, 1, 0, 1, 0, 0, 0, 1]).unwrap();
+        let lines = board.winning_lines();
+        assert_eq!(lines.len() % 3, 0);
+        assert_eq!(lines, vec![0, 1, 2, 0, 4, 8]);
+        assert!(WasmBoard::new().winning_lines().is_empty());
+    }
+
+    #[test]
+    fn test_make_move_after_win() {
+        let mut board = WasmBoard::new();
+        for idx in [0, 1, 2] {
+            assert!(board.make_move(idx, 1));
+        }
+        let before = board.get_cells();
+        assert!(!board.make_move(4, 2));
+        assert_eq!(board.get_cells(), before);
+    }
+
+    #[test]
+    fn test_best_move_with_difficulty() {
+        let mut board = WasmBoard::new();
+        board.set_seed(3);
+        board.make_move(4, 1);
+        board.make_move(0, 2);
+
+        let cells = board.get_cells();
+        for _ in 0..50 {
+            let idx = board.best_move_with_difficulty(1, 0).unwrap();
+            assert_eq!(cells[idx], 0);
+        }
+        assert_eq!(board.best_move_with_difficulty(0, 0), None);
+        assert_eq!(board.best_move_with_difficulty(1, 3), None);
+    }
+}